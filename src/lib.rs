@@ -9,6 +9,36 @@ use adblock::content_blocking::{CbRuleEquivalent, CbRuleCreationFailure};
 
 mod util;
 
+/// A single entry from Brave's `list_catalog.json`, i.e. one filter list component that may be
+/// made up of several source files.
+#[derive(Clone, PartialEq, serde::Deserialize)]
+struct CatalogEntry {
+    title: Option<String>,
+    sources: Vec<CatalogSource>,
+}
+
+#[derive(Clone, PartialEq, serde::Deserialize)]
+struct CatalogSource {
+    url: String,
+}
+
+/// The subset of `Model`'s state that gets round-tripped through the page's URL fragment, so
+/// that a link reproduces a scenario exactly. Large state (the filter list text, resources,
+/// results) is intentionally excluded to keep the URL short.
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+struct PermalinkState {
+    #[serde(default)]
+    filter: String,
+    #[serde(default)]
+    network_url: String,
+    #[serde(default)]
+    network_source_url: String,
+    #[serde(default)]
+    network_request_type: String,
+    #[serde(default)]
+    cosmetic_url: String,
+}
+
 struct Model {
     filter: String,
     parse_result: Result<ParsedFilter, FilterParseError>,
@@ -19,6 +49,13 @@ struct Model {
     engine: adblock::Engine,
     metadata: adblock::lists::FilterListMetadata,
 
+    filter_list_url: String,
+    filter_list_fetch_error: Option<String>,
+
+    catalog: Vec<CatalogEntry>,
+    catalog_error: Option<String>,
+    catalog_selection: std::collections::HashSet<usize>,
+
     network_url: String,
     network_source_url: String,
     network_request_type: String,
@@ -27,19 +64,78 @@ struct Model {
     cosmetic_url: String,
     cosmetic_result: Option<adblock::cosmetic_filter_cache::UrlSpecificResources>,
 
+    network_batch_input: String,
+    network_batch_result: Vec<NetworkBatchRow>,
+
     resources: Vec<adblock::resources::Resource>,
+
+    dat_load_error: Option<String>,
+
+    benchmark_requests_input: String,
+    benchmark_repeat_count: String,
+    benchmark_result: Option<BenchmarkResult>,
+    benchmark_error: Option<String>,
+}
+
+/// Timing results from a single run of the in-browser matching benchmark: how long it took to
+/// compile the currently loaded list into an `Engine`, and how long it took to match the
+/// requests under test against the live `Engine`.
+struct BenchmarkResult {
+    compile_ms: f64,
+    match_request_count: usize,
+    match_total_ms: f64,
+    match_mean_ms: f64,
+    match_requests_per_second: f64,
+}
+
+/// The outcome of matching a single row of a pasted `url, source_url, request_type` batch
+/// against the live `Engine`, or the parse error if the row was malformed.
+struct NetworkBatchRow {
+    url: String,
+    source_url: String,
+    request_type: String,
+    result: Result<adblock::blocker::BlockerResult, String>,
+}
+
+impl NetworkBatchRow {
+    /// `"Blocked"`, `"Allowed"`, `"Allowed (exception)"`, for a matched row, or `None` if the
+    /// row failed to parse.
+    fn status(&self) -> Option<&'static str> {
+        self.result.as_ref().ok().map(|r| {
+            if r.exception.is_some() {
+                "Allowed (exception)"
+            } else if r.matched {
+                "Blocked"
+            } else {
+                "Allowed"
+            }
+        })
+    }
 }
 
 enum Msg {
     UpdateFilter(String),
     UpdateFilterList(String),
     FilterListTimeout,
+    UpdateFilterListUrl(String),
+    FetchFilterListUrl,
+    FilterListUrlFetched(Result<String, String>),
+    LoadCatalogJson(String),
+    ToggleCatalogEntry(usize),
+    FetchCatalogSelection,
+    CatalogSourcesFetched(Result<String, String>),
     UpdateNetworkUrl(String),
     UpdateNetworkSourceUrl(String),
     UpdateNetworkRequestType(String),
     UpdateCosmeticUrl(String),
     LoadResourcesJson(String),
     DownloadDat,
+    LoadDat(Vec<u8>),
+    UpdateBenchmarkRequestsInput(String),
+    UpdateBenchmarkRepeatCount(String),
+    RunBenchmark,
+    UpdateNetworkBatchInput(String),
+    CheckNetworkBatch,
 }
 
 const FILTER_LIST_UPDATE_DEBOUNCE_MS: u32 = 1200;
@@ -48,7 +144,7 @@ impl Component for Model {
     type Message = Msg;
     type Properties = ();
     fn create(_ctx: &Context<Self>) -> Self {
-        Self {
+        let mut model = Self {
             filter: "".into(),
             parse_result: Err(FilterParseError::Empty),
             cb_result: None,
@@ -58,6 +154,13 @@ impl Component for Model {
             engine: adblock::Engine::new(false),
             metadata: adblock::lists::FilterListMetadata::default(),
 
+            filter_list_url: String::new(),
+            filter_list_fetch_error: None,
+
+            catalog: vec![],
+            catalog_error: None,
+            catalog_selection: std::collections::HashSet::new(),
+
             network_url: String::new(),
             network_source_url: String::new(),
             network_request_type: String::new(),
@@ -66,8 +169,20 @@ impl Component for Model {
             cosmetic_url: String::new(),
             cosmetic_result: None,
 
+            network_batch_input: String::new(),
+            network_batch_result: vec![],
+
             resources: vec![],
-        }
+
+            dat_load_error: None,
+
+            benchmark_requests_input: String::new(),
+            benchmark_repeat_count: String::new(),
+            benchmark_result: None,
+            benchmark_error: None,
+        };
+        model.load_permalink();
+        model
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
@@ -95,11 +210,73 @@ impl Component for Model {
                 ));
             }
             Msg::FilterListTimeout => {
-                let mut filter_set = adblock::lists::FilterSet::new(true);
-                self.metadata = filter_set.add_filter_list(&self.filter_list, ParseOptions::default());
-                self.engine = adblock::Engine::from_filter_set(filter_set, false);
-                self.engine.use_resources(self.resources.iter().map(|r| r.clone()));
-                self.check_network_urls();
+                self.rebuild_engine_from_filter_list();
+            }
+            Msg::UpdateFilterListUrl(new_value) => {
+                self.filter_list_url = new_value;
+            }
+            Msg::FetchFilterListUrl => {
+                self.filter_list_fetch_error = None;
+                let link = ctx.link().clone();
+                let url = self.filter_list_url.clone();
+                wasm_bindgen_futures::spawn_local(async move {
+                    let result = fetch_text(&url).await;
+                    link.send_message(Msg::FilterListUrlFetched(result));
+                });
+            }
+            Msg::FilterListUrlFetched(Ok(text)) => {
+                self.filter_list = text;
+                self.rebuild_engine_from_filter_list();
+            }
+            Msg::FilterListUrlFetched(Err(e)) => {
+                self.filter_list_fetch_error = Some(e);
+            }
+            Msg::LoadCatalogJson(new_value) => {
+                match serde_json::from_str::<Vec<CatalogEntry>>(&new_value) {
+                    Ok(catalog) => {
+                        self.catalog = catalog;
+                        self.catalog_selection.clear();
+                        self.catalog_error = None;
+                    }
+                    Err(e) => {
+                        self.catalog_error = Some(format!("{}", e));
+                    }
+                }
+            }
+            Msg::ToggleCatalogEntry(index) => {
+                if !self.catalog_selection.remove(&index) {
+                    self.catalog_selection.insert(index);
+                }
+            }
+            Msg::FetchCatalogSelection => {
+                self.filter_list_fetch_error = None;
+                let urls: Vec<String> = self.catalog_selection.iter()
+                    .filter_map(|i| self.catalog.get(*i))
+                    .flat_map(|entry| entry.sources.iter().map(|s| s.url.clone()))
+                    .collect();
+                let link = ctx.link().clone();
+                wasm_bindgen_futures::spawn_local(async move {
+                    let fetches = urls.iter().map(|url| fetch_text(url));
+                    let results = futures::future::join_all(fetches).await;
+                    let mut lists = Vec::with_capacity(results.len());
+                    for result in results {
+                        match result {
+                            Ok(text) => lists.push(text),
+                            Err(e) => {
+                                link.send_message(Msg::CatalogSourcesFetched(Err(e)));
+                                return;
+                            }
+                        }
+                    }
+                    link.send_message(Msg::CatalogSourcesFetched(Ok(lists.join("\n"))));
+                });
+            }
+            Msg::CatalogSourcesFetched(Ok(text)) => {
+                self.filter_list = text;
+                self.rebuild_engine_from_filter_list();
+            }
+            Msg::CatalogSourcesFetched(Err(e)) => {
+                self.filter_list_fetch_error = Some(e);
             }
             Msg::UpdateNetworkUrl(new_value) => {
                 self.network_url = new_value;
@@ -126,7 +303,105 @@ impl Component for Model {
                 let data = self.engine.serialize_raw().unwrap();
                 util::save_bin_file("rs-ABPFilterParserData.dat", &data[..]);
             }
+            Msg::LoadDat(bytes) => {
+                let mut engine = adblock::Engine::new(false);
+                match engine.deserialize(&bytes) {
+                    Ok(()) => {
+                        self.engine = engine;
+                        self.engine.use_resources(self.resources.iter().map(|r| r.clone()));
+                        self.dat_load_error = None;
+                        self.check_network_urls();
+                        if !self.cosmetic_url.is_empty() {
+                            self.cosmetic_result = Some(self.engine.url_cosmetic_resources(&self.cosmetic_url));
+                        }
+                    }
+                    Err(e) => {
+                        self.dat_load_error = Some(format!("{:?}", e));
+                    }
+                }
+            }
+            Msg::UpdateBenchmarkRequestsInput(new_value) => {
+                self.benchmark_requests_input = new_value;
+            }
+            Msg::UpdateBenchmarkRepeatCount(new_value) => {
+                self.benchmark_repeat_count = new_value;
+            }
+            Msg::RunBenchmark => {
+                self.benchmark_error = None;
+                self.benchmark_result = None;
+
+                let lines: Vec<&str> = self.benchmark_requests_input.lines().map(str::trim).filter(|l| !l.is_empty()).collect();
+                let triples: Vec<(String, String, String)> = if !lines.is_empty() {
+                    match lines.iter().map(|l| parse_request_line(l)).collect::<Result<Vec<_>, _>>() {
+                        Ok(triples) => triples,
+                        Err(e) => {
+                            self.benchmark_error = Some(e);
+                            vec![]
+                        }
+                    }
+                } else {
+                    let repeat: usize = self.benchmark_repeat_count.parse().unwrap_or(0);
+                    std::iter::repeat((self.network_url.clone(), self.network_source_url.clone(), self.network_request_type.clone()))
+                        .take(repeat)
+                        .collect()
+                };
+
+                if self.benchmark_error.is_none() {
+                    if triples.is_empty() {
+                        self.benchmark_error = Some("No requests to benchmark: paste some lines, or set a repeat count with a request under test above".into());
+                    } else {
+                        let requests: Vec<_> = triples.iter()
+                            .filter_map(|(u, s, t)| adblock::request::Request::new(u, s, t).ok())
+                            .collect();
+
+                        let performance = web_sys::window().unwrap().performance().unwrap();
+
+                        let compile_start = performance.now();
+                        let mut filter_set = adblock::lists::FilterSet::new(true);
+                        filter_set.add_filter_list(&self.filter_list, ParseOptions::default());
+                        let _compiled_engine = adblock::Engine::from_filter_set(filter_set, false);
+                        let compile_ms = performance.now() - compile_start;
+
+                        let match_start = performance.now();
+                        for request in &requests {
+                            self.engine.check_network_request(request);
+                        }
+                        let match_total_ms = performance.now() - match_start;
+
+                        let match_request_count = requests.len();
+                        let match_mean_ms = if match_request_count > 0 { match_total_ms / match_request_count as f64 } else { 0.0 };
+                        let match_requests_per_second = if match_total_ms > 0.0 { match_request_count as f64 / (match_total_ms / 1000.0) } else { 0.0 };
+
+                        self.benchmark_result = Some(BenchmarkResult {
+                            compile_ms,
+                            match_request_count,
+                            match_total_ms,
+                            match_mean_ms,
+                            match_requests_per_second,
+                        });
+                    }
+                }
+            }
+            Msg::UpdateNetworkBatchInput(new_value) => {
+                self.network_batch_input = new_value;
+            }
+            Msg::CheckNetworkBatch => {
+                self.network_batch_result = self.network_batch_input.lines()
+                    .map(str::trim)
+                    .filter(|l| !l.is_empty())
+                    .map(|line| match parse_request_line(line) {
+                        Ok((url, source_url, request_type)) => {
+                            let result = adblock::request::Request::new(&url, &source_url, &request_type)
+                                .map_err(|e| format!("{}", e))
+                                .map(|request| self.engine.check_network_request(&request));
+                            NetworkBatchRow { url, source_url, request_type, result }
+                        }
+                        Err(e) => NetworkBatchRow { url: line.to_string(), source_url: String::new(), request_type: String::new(), result: Err(e) },
+                    })
+                    .collect();
+            }
         }
+        self.write_permalink();
         true
     }
 
@@ -171,9 +446,82 @@ impl Component for Model {
                     } else {
                         html! { <></> }
                     } }
+
+                    { if let Ok(ParsedFilter::Cosmetic(_)) = &self.parse_result {
+                        if let Some((name, args)) = parse_scriptlet_syntax(&self.filter) {
+                            html! {
+                                <>
+                                    <h4>{"Scriptlet injection"}</h4>
+                                    { match resolve_scriptlet(&self.resources, &name, &args) {
+                                        Ok(js) => html! { <pre><code>{js}</code></pre> },
+                                        Err(e) => html! { <p>{"Error resolving scriptlet: "}<code class="error">{e}</code></p> },
+                                    } }
+                                </>
+                            }
+                        } else {
+                            html! { <></> }
+                        }
+                    } else {
+                        html! { <></> }
+                    } }
                 </div>
                 <div>
                     <h2>{"Test a list"}</h2>
+                    <h3>{"Fetch a list from a URL"}</h3>
+                    <input type="text" placeholder="https://example.com/list.txt" value={self.filter_list_url.clone()} oninput={ctx.link().callback(|e: InputEvent| Msg::UpdateFilterListUrl(e.target().unwrap().dyn_into::<web_sys::HtmlInputElement>().unwrap().value()))}/>
+                    <button onclick={ctx.link().callback(|_e: MouseEvent| Msg::FetchFilterListUrl)}>{"Fetch"}</button>
+                    { if let Some(e) = &self.filter_list_fetch_error {
+                        html! { <p>{"Error fetching list: "}<code class="error">{e.clone()}</code></p> }
+                    } else {
+                        html! {}
+                    } }
+                    <h3>{"Browse the Brave list catalog"}</h3>
+                    <input type="file" accept=".json,application/json" id="load_catalog_json" oninput={
+                        let link = ctx.link().clone();
+                        move |e: InputEvent| {
+                            let link = link.clone();
+                            let input_element = e.target().unwrap().dyn_into::<web_sys::HtmlInputElement>().unwrap();
+                            if let Some(file) = input_element.files().unwrap().item(0) {
+                                unsafe {
+                                    read_file_text_and_then(&file, move |text| {
+                                        let link = link.clone();
+                                        link.send_message(Msg::LoadCatalogJson(text));
+                                    });
+                                }
+                            }
+                            input_element.set_value("");
+                        }
+                    }/>
+                    <div>
+                        <label for="load_catalog_json"><span>{"Load "}</span><code>{"list_catalog.json"}</code></label>
+                    </div>
+                    { if let Some(e) = &self.catalog_error {
+                        html! { <p>{"Error parsing catalog: "}<code class="error">{e.clone()}</code></p> }
+                    } else {
+                        html! {}
+                    } }
+                    { if !self.catalog.is_empty() {
+                        html! {
+                            <>
+                                <ul>
+                                    { for self.catalog.iter().enumerate().map(|(i, entry)| {
+                                        let checked = self.catalog_selection.contains(&i);
+                                        html! {
+                                            <li>
+                                                <label>
+                                                    <input type="checkbox" checked={checked} onclick={ctx.link().callback(move |_| Msg::ToggleCatalogEntry(i))}/>
+                                                    { entry.title.clone().unwrap_or_else(|| format!("({} sources, untitled)", entry.sources.len())) }
+                                                </label>
+                                            </li>
+                                        }
+                                    }) }
+                                </ul>
+                                <button onclick={ctx.link().callback(|_e: MouseEvent| Msg::FetchCatalogSelection)}>{"Fetch selected lists"}</button>
+                            </>
+                        }
+                    } else {
+                        html! {}
+                    } }
                     <h3>{"List contents"}</h3>
                     <textarea value={self.filter_list.clone()} oninput={ctx.link().callback(|e: InputEvent| Msg::UpdateFilterList(e.target().unwrap().dyn_into::<web_sys::HtmlTextAreaElement>().unwrap().value()))}/>
                     <input type="file" accept=".json,application/json" id="load_resources_json" oninput={
@@ -226,6 +574,52 @@ impl Component for Model {
                             None => html! { <p></p> },
                         }
                     }
+                    <h3>{"Check a batch of network requests"}</h3>
+                    <p><i>{"One `url, source_url, request_type` per line."}</i></p>
+                    <textarea placeholder="https://tracker.example/pixel.gif, https://example.com, image" value={self.network_batch_input.clone()} oninput={ctx.link().callback(|e: InputEvent| Msg::UpdateNetworkBatchInput(e.target().unwrap().dyn_into::<web_sys::HtmlTextAreaElement>().unwrap().value()))}/>
+                    <div><button onclick={ctx.link().callback(|_e: MouseEvent| Msg::CheckNetworkBatch)}>{"Check batch"}</button></div>
+                    { if !self.network_batch_result.is_empty() {
+                        let blocked = self.network_batch_result.iter().filter(|r| r.status() == Some("Blocked")).count();
+                        let allowed = self.network_batch_result.iter().filter(|r| matches!(r.status(), Some("Allowed") | Some("Allowed (exception)"))).count();
+                        let errored = self.network_batch_result.iter().filter(|r| r.result.is_err()).count();
+                        html! {
+                            <>
+                                <p>{format!("{} blocked, {} allowed, {} errored", blocked, allowed, errored)}</p>
+                                <table>
+                                    <tr>
+                                        <th>{"URL"}</th>
+                                        <th>{"Source URL"}</th>
+                                        <th>{"Type"}</th>
+                                        <th>{"Status"}</th>
+                                        <th>{"Matched filter"}</th>
+                                        <th>{"Exception"}</th>
+                                    </tr>
+                                    { for self.network_batch_result.iter().map(|row| {
+                                        match &row.result {
+                                            Ok(result) => html! {
+                                                <tr>
+                                                    <td>{row.url.clone()}</td>
+                                                    <td>{row.source_url.clone()}</td>
+                                                    <td>{row.request_type.clone()}</td>
+                                                    <td>{row.status().unwrap_or("")}</td>
+                                                    <td>{result.filter.clone().unwrap_or_default()}</td>
+                                                    <td>{result.exception.clone().unwrap_or_default()}</td>
+                                                </tr>
+                                            },
+                                            Err(e) => html! {
+                                                <tr>
+                                                    <td>{row.url.clone()}</td>
+                                                    <td colspan="5"><code class="error">{e.clone()}</code></td>
+                                                </tr>
+                                            },
+                                        }
+                                    }) }
+                                </table>
+                            </>
+                        }
+                    } else {
+                        html! {}
+                    } }
                     <h3>{"Check cosmetic resources"}</h3>
                     <h4>{"Source URL"}</h4>
                     <input type="text" value={self.cosmetic_url.clone()} oninput={ctx.link().callback(|e: InputEvent| Msg::UpdateCosmeticUrl(e.target().unwrap().dyn_into::<web_sys::HtmlInputElement>().unwrap().value()))}/>
@@ -248,8 +642,56 @@ impl Component for Model {
                             html! { <p></p> }
                         }
                     }
+                    <h3>{"Benchmark matching"}</h3>
+                    <p><i>{"Paste `url, source_url, request_type` lines below, or leave it empty and set a repeat count to replay the single request under test from the section above."}</i></p>
+                    <textarea placeholder="https://example.com/script.js, https://example.com, script" value={self.benchmark_requests_input.clone()} oninput={ctx.link().callback(|e: InputEvent| Msg::UpdateBenchmarkRequestsInput(e.target().unwrap().dyn_into::<web_sys::HtmlTextAreaElement>().unwrap().value()))}/>
+                    <h4>{"Repeat count"}</h4>
+                    <input type="text" placeholder="10000" value={self.benchmark_repeat_count.clone()} oninput={ctx.link().callback(|e: InputEvent| Msg::UpdateBenchmarkRepeatCount(e.target().unwrap().dyn_into::<web_sys::HtmlInputElement>().unwrap().value()))}/>
+                    <div><button onclick={ctx.link().callback(|_e: MouseEvent| Msg::RunBenchmark)}>{"Run benchmark"}</button></div>
+                    { if let Some(e) = &self.benchmark_error {
+                        html! { <p>{"Error: "}<code class="error">{e.clone()}</code></p> }
+                    } else {
+                        html! {}
+                    } }
+                    { if let Some(result) = &self.benchmark_result {
+                        html! {
+                            <ul>
+                                <li>{format!("List compilation: {:.2}ms", result.compile_ms)}</li>
+                                <li>{format!("Matched {} requests in {:.2}ms total", result.match_request_count, result.match_total_ms)}</li>
+                                <li>{format!("Mean latency: {:.4}ms/request", result.match_mean_ms)}</li>
+                                <li>{format!("Throughput: {:.0} requests/second", result.match_requests_per_second)}</li>
+                            </ul>
+                        }
+                    } else {
+                        html! {}
+                    } }
                     <h3>{"Download the serialized DAT"}</h3>
                     <button onclick={ctx.link().callback(|_e: MouseEvent| Msg::DownloadDat)}>{"Download"}</button>
+                    <h3>{"Load a serialized DAT"}</h3>
+                    <input type="file" accept=".dat" id="load_dat" oninput={
+                        let link = ctx.link().clone();
+                        move |e: InputEvent| {
+                            let link = link.clone();
+                            let input_element = e.target().unwrap().dyn_into::<web_sys::HtmlInputElement>().unwrap();
+                            if let Some(file) = input_element.files().unwrap().item(0) {
+                                unsafe {
+                                    read_file_binary_and_then(&file, move |bytes| {
+                                        let link = link.clone();
+                                        link.send_message(Msg::LoadDat(bytes));
+                                    });
+                                }
+                            }
+                            input_element.set_value("");
+                        }
+                    }/>
+                    <div>
+                        <label for="load_dat"><span>{"Load a "}</span><code>{".dat"}</code><span>{" file"}</span></label>
+                    </div>
+                    { if let Some(e) = &self.dat_load_error {
+                        html! { <p>{"Error deserializing DAT: "}<code class="error">{e.clone()}</code></p> }
+                    } else {
+                        html! {}
+                    } }
                 </div>
             </>
         }
@@ -281,6 +723,68 @@ impl Model {
             </>
         }
     }
+    /// Reads a permalink fragment from the page's current URL, if any, and restores the
+    /// scenario it describes: the single-filter input, the network request triple, and the
+    /// cosmetic source URL, along with the results each of those would normally produce.
+    fn load_permalink(&mut self) {
+        let Some(state) = Self::read_permalink_fragment() else { return };
+        self.filter = state.filter;
+        self.network_url = state.network_url;
+        self.network_source_url = state.network_source_url;
+        self.network_request_type = state.network_request_type;
+        self.cosmetic_url = state.cosmetic_url;
+
+        let parse_options = ParseOptions { rule_types: RuleTypes::All, format: FilterFormat::Standard, permissions: PermissionMask::from_bits(0) };
+        self.parse_result = parse_filter(&self.filter, true, parse_options);
+        self.cb_result = parse_filter(&self.filter, true, parse_options).ok().map(|r| r.try_into());
+        self.check_network_urls();
+        if !self.cosmetic_url.is_empty() {
+            self.cosmetic_result = Some(self.engine.url_cosmetic_resources(&self.cosmetic_url));
+        }
+    }
+
+    fn read_permalink_fragment() -> Option<PermalinkState> {
+        let hash = web_sys::window()?.location().hash().ok()?;
+        let encoded = hash.strip_prefix('#')?;
+        if encoded.is_empty() {
+            return None;
+        }
+        let bytes = base64::decode(encoded).ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+
+    /// Serializes the shareable subset of state into the page's URL fragment via
+    /// `History::replace_state_with_url`, so that every interaction produces a linkable,
+    /// reproducible test case without adding a new history entry per keystroke.
+    fn write_permalink(&self) {
+        let state = PermalinkState {
+            filter: self.filter.clone(),
+            network_url: self.network_url.clone(),
+            network_source_url: self.network_source_url.clone(),
+            network_request_type: self.network_request_type.clone(),
+            cosmetic_url: self.cosmetic_url.clone(),
+        };
+        let Ok(json) = serde_json::to_vec(&state) else { return };
+        let encoded = base64::encode(json);
+
+        let Some(window) = web_sys::window() else { return };
+        let Ok(location) = window.location().href() else { return };
+        let base = location.split('#').next().unwrap_or(&location);
+        let url = format!("{}#{}", base, encoded);
+
+        if let Ok(history) = window.history() {
+            let _ = history.replace_state_with_url(&JsValue::NULL, "", Some(&url));
+        }
+    }
+
+    fn rebuild_engine_from_filter_list(&mut self) {
+        let mut filter_set = adblock::lists::FilterSet::new(true);
+        self.metadata = filter_set.add_filter_list(&self.filter_list, ParseOptions::default());
+        self.engine = adblock::Engine::from_filter_set(filter_set, false);
+        self.engine.use_resources(self.resources.iter().map(|r| r.clone()));
+        self.check_network_urls();
+    }
+
     fn check_network_urls(&mut self) {
         self.network_result = if self.network_url.is_empty() && self.network_source_url.is_empty() && self.network_request_type.is_empty() {
             None
@@ -323,6 +827,105 @@ impl Model {
     }
 }
 
+/// Extracts the scriptlet name and positional arguments out of a raw `+js(...)` cosmetic filter,
+/// e.g. `example.com##+js(name, arg1, arg2)` yields `("name", vec!["arg1", "arg2"])`.
+fn parse_scriptlet_syntax(filter: &str) -> Option<(String, Vec<String>)> {
+    let args_start = filter.find("+js(")? + "+js(".len();
+    let args_end = args_start + filter[args_start..].find(')')?;
+    let mut parts = filter[args_start..args_end].split(',').map(|s| s.trim().to_string());
+    let name = parts.next()?;
+    if name.is_empty() {
+        return None;
+    }
+    Some((name, parts.filter(|a| !a.is_empty()).collect()))
+}
+
+/// Looks up a scriptlet resource by `token`, first by exact `name`, then by `aliases`, retrying
+/// both with a `.js` suffix appended to `token` if nothing matched, mirroring the lookup the
+/// engine itself performs when injecting `+js(...)` rules.
+fn find_scriptlet_resource<'a>(resources: &'a [adblock::resources::Resource], token: &str) -> Option<&'a adblock::resources::Resource> {
+    fn find<'a>(resources: &'a [adblock::resources::Resource], token: &str) -> Option<&'a adblock::resources::Resource> {
+        resources.iter().find(|r| r.name == token)
+            .or_else(|| resources.iter().find(|r| r.aliases.iter().any(|alias| alias == token)))
+    }
+    find(resources, token)
+        .or_else(|| {
+            let with_ext = format!("{}.js", token);
+            find(resources, &with_ext)
+        })
+}
+
+/// Counts the highest `{{N}}` placeholder referenced in a scriptlet template, to detect
+/// argument-count mismatches before substitution.
+fn max_placeholder_index(template: &str) -> usize {
+    let mut max = 0;
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        match after.find("}}") {
+            Some(end) => {
+                if let Ok(n) = after[..end].parse::<usize>() {
+                    max = max.max(n);
+                }
+                rest = &after[end + 2..];
+            }
+            None => break,
+        }
+    }
+    max
+}
+
+/// Resolves a `+js(name, args...)` scriptlet call against the loaded `resources`, returning the
+/// final injected JavaScript, or a human-readable error if the name is unknown, the resource
+/// isn't a scriptlet, or too few arguments were supplied.
+fn resolve_scriptlet(resources: &[adblock::resources::Resource], name: &str, args: &[String]) -> Result<String, String> {
+    let resource = find_scriptlet_resource(resources, name)
+        .ok_or_else(|| format!("No resource named `{}` (checked aliases and `.js` suffix)", name))?;
+
+    if !matches!(&resource.kind, adblock::resources::ResourceType::Mime(adblock::resources::MimeType::ApplicationJavascript)) {
+        return Err(format!("Resource `{}` is not a scriptlet (kind is not application/javascript)", resource.name));
+    }
+
+    let decoded = base64::decode(&resource.content).map_err(|e| format!("Failed to decode resource content: {}", e))?;
+    let template = String::from_utf8(decoded).map_err(|e| format!("Resource content is not valid UTF-8: {}", e))?;
+
+    let needed = max_placeholder_index(&template);
+    if args.len() < needed {
+        return Err(format!("Scriptlet `{}` expects at least {} argument(s), got {}", name, needed, args.len()));
+    }
+
+    let mut result = template;
+    for (i, arg) in args.iter().enumerate() {
+        result = result.replace(&format!("{{{{{}}}}}", i + 1), arg);
+    }
+    Ok(result)
+}
+
+/// Parses a single `url, source_url, request_type` row, as used by the benchmark and batch
+/// request panels.
+fn parse_request_line(line: &str) -> Result<(String, String, String), String> {
+    let parts: Vec<&str> = line.splitn(3, ',').map(|s| s.trim()).collect();
+    match parts.as_slice() {
+        [url, source_url, request_type] if !url.is_empty() => {
+            Ok((url.to_string(), source_url.to_string(), request_type.to_string()))
+        }
+        _ => Err(format!("Expected `url, source_url, request_type`, got `{}`", line)),
+    }
+}
+
+/// Fetches the body of `url` as text, mapping any network or status error to a displayable
+/// `String` so callers can report it directly in the view.
+async fn fetch_text(url: &str) -> Result<String, String> {
+    let response = gloo_net::http::Request::get(url)
+        .send()
+        .await
+        .map_err(|e| format!("{}", e))?;
+    if !response.ok() {
+        return Err(format!("Request failed with status {}", response.status()));
+    }
+    response.text().await.map_err(|e| format!("{}", e))
+}
+
 /// Reads a file and then executes a closure on the text contents using `FileReader`.
 unsafe fn read_file_text_and_then(file: &web_sys::File, closure: impl FnOnce(String) + 'static) {
     fn onload_helper(e: ProgressEvent, closure: impl FnOnce(String)) {
@@ -339,6 +942,23 @@ unsafe fn read_file_text_and_then(file: &web_sys::File, closure: impl FnOnce(Str
     filereader.read_as_text(file).unwrap();
 }
 
+/// Reads a file and then executes a closure on the binary contents using `FileReader`.
+unsafe fn read_file_binary_and_then(file: &web_sys::File, closure: impl FnOnce(Vec<u8>) + 'static) {
+    fn onload_helper(e: ProgressEvent, closure: impl FnOnce(Vec<u8>)) {
+        let buffer = e.target().unwrap().dyn_into::<web_sys::FileReader>().unwrap().result().unwrap();
+        let bytes = web_sys::js_sys::Uint8Array::new(&buffer).to_vec();
+        closure(bytes);
+    }
+
+    let filereader = web_sys::FileReader::new().unwrap();
+    let closure = wasm_bindgen::closure::Closure::once(move |e: ProgressEvent| {
+        onload_helper(e, closure);
+    }).into_js_value().dyn_into::<web_sys::js_sys::Function>().unwrap();
+    filereader.set_onload(Some(&closure));
+
+    filereader.read_as_array_buffer(file).unwrap();
+}
+
 #[wasm_bindgen(start)]
 pub fn run_app() {
     std::panic::set_hook(Box::new(console_error_panic_hook::hook));